@@ -0,0 +1,23 @@
+use super::Op;
+
+/// A client strategy that issues several operations as a single atomic batch.
+///
+/// The two puts share a transaction so the atomicity property can assert that
+/// a peer never observes one key without the other.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BatchClient {
+    pub key: String,
+    pub other_key: String,
+    pub value: String,
+    pub request_count: usize,
+}
+
+impl BatchClient {
+    /// The operations making up one atomic transaction from this client.
+    pub fn ops(&self) -> Vec<Op> {
+        vec![
+            Op::PutMap(self.key.clone(), self.value.clone()),
+            Op::PutMap(self.other_key.clone(), self.value.clone()),
+        ]
+    }
+}
@@ -0,0 +1,6 @@
+/// A client strategy that just increments a counter by a fixed delta.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct IncrementClient {
+    pub delta: i64,
+    pub request_count: usize,
+}
@@ -0,0 +1,7 @@
+/// A client strategy that just inserts a single element in a list.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ListInserter {
+    pub index: usize,
+    pub value: String,
+    pub request_count: usize,
+}
@@ -0,0 +1,58 @@
+mod batch;
+mod delete;
+mod increment;
+mod insert;
+mod put;
+mod text;
+
+pub use batch::BatchClient;
+pub use delete::{ListDeleter, MapSingleDeleter};
+pub use increment::IncrementClient;
+pub use insert::ListInserter;
+pub use put::MapSinglePutter;
+pub use text::{TextDeleter, TextInserter};
+
+/// A single operation that can take part in an atomic [`Request::Transaction`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Op {
+    PutMap(String, String),
+    DeleteMap(String),
+    InsertList(usize, String),
+    DeleteList(usize),
+}
+
+/// A message exchanged in the model: client requests, server acknowledgements,
+/// and server-to-server sync traffic all share one type so every actor in the
+/// [`crate::model`] speaks the same `Msg`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Request {
+    PutMap(String, String),
+    DeleteMap(String),
+    InsertList(usize, String),
+    DeleteList(usize),
+    /// Several operations applied as a single atomic unit (one commit).
+    Transaction(Vec<Op>),
+    /// Increment the counter by a signed delta.
+    Increment(i64),
+    /// Insert a string at a character position in the text.
+    InsertText(usize, String),
+    /// Delete the character at a position in the text.
+    DeleteText(usize),
+    /// An acknowledgement sent back to a client.
+    Response(Response),
+    /// Sync traffic between servers.
+    Sync(crate::server::SyncMessage),
+    /// A membership transition aimed at a server.
+    Lifecycle(crate::server::Lifecycle),
+}
+
+/// A response from the server acknowledging a client [`Request`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Response {
+    PutOk,
+    DeleteOk,
+    InsertOk,
+    TransactionOk,
+    IncrementOk,
+    TextOk,
+}
@@ -0,0 +1,7 @@
+/// A client strategy that just puts a single key in a map.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MapSinglePutter {
+    pub key: String,
+    pub value: String,
+    pub request_count: usize,
+}
@@ -0,0 +1,14 @@
+/// A client strategy that just inserts a character at a text position.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TextInserter {
+    pub index: usize,
+    pub value: String,
+    pub request_count: usize,
+}
+
+/// A client strategy that just deletes the character at a text position.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TextDeleter {
+    pub index: usize,
+    pub request_count: usize,
+}
@@ -0,0 +1,186 @@
+use automerge::transaction::Transactable;
+use automerge::AutoCommit;
+use automerge::ObjType;
+use automerge::ReadDoc;
+use automerge::ScalarValue;
+use automerge::Value;
+use automerge::ROOT;
+
+/// Well-known keys under the document root for the objects the checker drives.
+const MAP_KEY: &str = "map";
+const LIST_KEY: &str = "list";
+const COUNTER_KEY: &str = "counter";
+const TEXT_KEY: &str = "text";
+
+/// A thin wrapper around an Automerge document exposing the operations the
+/// client strategies and server speak in terms of.
+#[derive(Clone, Debug)]
+pub struct Doc {
+    am: AutoCommit,
+}
+
+impl Default for Doc {
+    fn default() -> Self {
+        let mut am = AutoCommit::new();
+        am.put_object(ROOT, MAP_KEY, ObjType::Map).unwrap();
+        am.put_object(ROOT, LIST_KEY, ObjType::List).unwrap();
+        am.put(ROOT, COUNTER_KEY, ScalarValue::Counter(0.into()))
+            .unwrap();
+        am.put_object(ROOT, TEXT_KEY, ObjType::Text).unwrap();
+        am.commit();
+        Self { am }
+    }
+}
+
+impl PartialEq for Doc {
+    fn eq(&self, other: &Self) -> bool {
+        // Two documents are equal when they materialize to the same heads.
+        self.heads() == other.heads()
+    }
+}
+
+impl Eq for Doc {}
+
+impl std::hash::Hash for Doc {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.heads().hash(state)
+    }
+}
+
+impl Doc {
+    /// The current document heads, identifying the materialized state.
+    pub fn heads(&self) -> Vec<automerge::ChangeHash> {
+        let mut am = self.am.clone();
+        am.get_heads()
+    }
+
+    /// Persist the document to bytes, as a crashed server would have on disk.
+    pub fn save(&mut self) -> Vec<u8> {
+        self.am.save()
+    }
+
+    /// Restore a document from a previous [`Doc::save`].
+    pub fn load(bytes: &[u8]) -> Self {
+        Self {
+            am: AutoCommit::load(bytes).unwrap(),
+        }
+    }
+
+    fn map(&self) -> automerge::ObjId {
+        self.am.get(ROOT, MAP_KEY).unwrap().unwrap().1
+    }
+
+    fn list(&self) -> automerge::ObjId {
+        self.am.get(ROOT, LIST_KEY).unwrap().unwrap().1
+    }
+
+    fn text(&self) -> automerge::ObjId {
+        self.am.get(ROOT, TEXT_KEY).unwrap().unwrap().1
+    }
+
+    pub fn put_map(&mut self, key: &str, value: &str) {
+        let map = self.map();
+        self.am.put(map, key, value).unwrap();
+    }
+
+    pub fn delete_map(&mut self, key: &str) {
+        let map = self.map();
+        let _ = self.am.delete(map, key);
+    }
+
+    pub fn insert_list(&mut self, index: usize, value: &str) {
+        let list = self.list();
+        let index = index.min(self.am.length(&list));
+        self.am.insert(list, index, value).unwrap();
+    }
+
+    pub fn delete_list(&mut self, index: usize) {
+        let list = self.list();
+        if index < self.am.length(&list) {
+            let _ = self.am.delete(list, index);
+        }
+    }
+
+    /// Whether `key` is currently present in the map.
+    pub fn map_contains(&self, key: &str) -> bool {
+        let map = self.map();
+        self.am.get(&map, key).unwrap().is_some()
+    }
+
+    /// The current length of the list.
+    pub fn list_len(&self) -> usize {
+        let list = self.list();
+        self.am.length(&list)
+    }
+
+    /// The current text contents as a string.
+    pub fn text_string(&self) -> String {
+        let text = self.text();
+        self.am.text(&text).unwrap()
+    }
+
+    /// Increment the root counter by `delta`.
+    pub fn increment(&mut self, delta: i64) {
+        self.am.increment(ROOT, COUNTER_KEY, delta).unwrap();
+    }
+
+    /// The current counter value materialized as an integer.
+    pub fn counter(&self) -> i64 {
+        match self.am.get(ROOT, COUNTER_KEY).unwrap() {
+            Some((Value::Scalar(s), _)) => match s.as_ref() {
+                ScalarValue::Counter(c) => c.into(),
+                ScalarValue::Int(i) => *i,
+                _ => 0,
+            },
+            _ => 0,
+        }
+    }
+
+    pub fn insert_text(&mut self, index: usize, value: &str) {
+        let text = self.text();
+        let index = index.min(self.am.length(&text));
+        self.am.splice_text(text, index, 0, value).unwrap();
+    }
+
+    pub fn delete_text(&mut self, index: usize) {
+        let text = self.text();
+        if index < self.am.length(&text) {
+            self.am.splice_text(text, index, 1, "").unwrap();
+        }
+    }
+
+    /// Run `f` against the underlying document inside a single change, so the
+    /// whole closure commits atomically as one Automerge transaction.
+    pub fn transact<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        f(self);
+        self.am.commit();
+    }
+
+    /// The raw document for sync-protocol access.
+    pub fn automerge_mut(&mut self) -> &mut AutoCommit {
+        &mut self.am
+    }
+
+    /// Apply another document's changes into this one (full-change merge).
+    pub fn merge(&mut self, other: &mut Doc) {
+        self.am.merge(&mut other.am).unwrap();
+    }
+
+    /// Generate the next sync message for a peer, given the shared sync state.
+    /// `None` once the two sides' heads agree.
+    pub fn generate_sync_message(
+        &mut self,
+        state: &mut automerge::sync::State,
+    ) -> Option<automerge::sync::Message> {
+        self.am.sync().generate_sync_message(state)
+    }
+
+    /// Apply a sync message from a peer, advancing the shared sync state.
+    pub fn receive_sync_message(
+        &mut self,
+        state: &mut automerge::sync::State,
+        message: automerge::sync::Message,
+    ) {
+        self.am.sync().receive_sync_message(state, message).unwrap();
+    }
+}
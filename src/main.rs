@@ -33,12 +33,24 @@ struct Opts {
     #[clap(long, short, global = true, default_value = "2")]
     insert_clients: usize,
 
+    #[clap(long, short, global = true, default_value = "1")]
+    batch_clients: usize,
+
+    #[clap(long, global = true, default_value = "2")]
+    increment_clients: usize,
+
+    #[clap(long, global = true, default_value = "2")]
+    text_clients: usize,
+
     #[clap(long, short, global = true, default_value = "2")]
     servers: usize,
 
     #[clap(long, global = true)]
     message_acks: bool,
 
+    // `changes` broadcasts all changes; `bloom-sync` models Automerge's
+    // two-party Bloom-filter handshake (sender heads + filter, reply with the
+    // changes the filter says are missing, plus need-request follow-up rounds).
     #[clap(long, arg_enum, global = true, default_value = "changes")]
     sync_method: SyncMethod,
 
@@ -46,14 +58,49 @@ struct Opts {
     #[clap(long, arg_enum, global = true, default_value = "map")]
     object_type: ObjectType,
 
+    // Which server lifecycle transitions the checker may explore.
+    #[clap(long, arg_enum, global = true, default_value = "static")]
+    server_lifecycle: ServerLifecycle,
+
+    // How the reporter surfaces results: human-oriented progress, or a
+    // structured JSON record aggregating the check for CI ingestion.
+    #[clap(long, arg_enum, global = true, default_value = "text")]
+    report_format: ReportFormat,
+
+    // Where a `json` report is written; stdout when unset.
+    #[clap(long, global = true)]
+    report_output: Option<std::path::PathBuf>,
+
     #[clap(long, default_value = "8080")]
     port: u16,
 }
 
+/// Output mode for the [`Reporter`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, clap::ArgEnum)]
+pub enum ReportFormat {
+    /// Human-oriented progress printed to the console.
+    Text,
+    /// A structured JSON record aggregating the whole check.
+    Json,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, clap::ArgEnum)]
 pub enum ObjectType {
     Map,
     List,
+    Counter,
+    Text,
+}
+
+/// Which dynamic-membership transitions the model checker is allowed to emit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, clap::ArgEnum)]
+pub enum ServerLifecycle {
+    /// Servers are fixed and always live (the original behaviour).
+    Static,
+    /// Server 0 may crash (dropping in-flight messages) and restart from its save.
+    CrashRestart,
+    /// As CrashRestart, and one extra server may join an already-diverged cluster.
+    Dynamic,
 }
 
 #[derive(clap::Subcommand, Debug)]
@@ -70,10 +117,14 @@ fn main() {
         put_clients: opts.put_clients,
         delete_clients: opts.delete_clients,
         insert_clients: opts.insert_clients,
+        batch_clients: opts.batch_clients,
+        increment_clients: opts.increment_clients,
+        text_clients: opts.text_clients,
         servers: opts.servers,
         sync_method: opts.sync_method,
         message_acks: opts.message_acks,
         object_type: opts.object_type,
+        server_lifecycle: opts.server_lifecycle,
     }
     .into_actor_model()
     .checker()
@@ -81,7 +132,10 @@ fn main() {
     run(opts, model)
 }
 
-fn run(opts: Opts, model: CheckerBuilder<ActorModel<MyRegisterActor, model::ModelConfig>>) {
+fn run(
+    opts: Opts,
+    model: CheckerBuilder<ActorModel<MyRegisterActor, model::ModelConfig, model::History>>,
+) {
     println!("Running with config {:?}", opts);
     match opts.command {
         SubCmd::Serve => {
@@ -89,18 +143,28 @@ fn run(opts: Opts, model: CheckerBuilder<ActorModel<MyRegisterActor, model::Mode
             model.serve(("127.0.0.1", opts.port));
         }
         SubCmd::CheckDfs => {
-            model
-                .spawn_dfs()
-                .report(&mut Reporter::default())
-                .join()
-                .assert_properties();
+            let mut reporter = Reporter::new(opts.report_format);
+            let start = std::time::Instant::now();
+            let checker = model.spawn_dfs().report(&mut reporter).join();
+            report::emit(
+                opts.report_format,
+                opts.report_output.as_deref(),
+                start.elapsed(),
+                &checker,
+            );
+            checker.assert_properties();
         }
         SubCmd::CheckBfs => {
-            model
-                .spawn_bfs()
-                .report(&mut Reporter::default())
-                .join()
-                .assert_properties();
+            let mut reporter = Reporter::new(opts.report_format);
+            let start = std::time::Instant::now();
+            let checker = model.spawn_bfs().report(&mut reporter).join();
+            report::emit(
+                opts.report_format,
+                opts.report_output.as_deref(),
+                start.elapsed(),
+                &checker,
+            );
+            checker.assert_properties();
         }
     }
 }
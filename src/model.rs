@@ -0,0 +1,274 @@
+use stateright::actor::model_peers;
+use stateright::actor::ActorModel;
+use stateright::actor::Id;
+use stateright::actor::Network;
+use stateright::Expectation;
+
+use crate::client::{
+    BatchClient, IncrementClient, ListDeleter, ListInserter, MapSingleDeleter, MapSinglePutter,
+    TextDeleter, TextInserter,
+};
+use crate::client::Request;
+use crate::register::{LifecycleDriver, MyRegisterActor, MyRegisterActorState};
+use crate::server::{Lifecycle, Server, SyncMethod};
+use crate::ObjectType;
+use crate::ServerLifecycle;
+
+/// Model history: the running count of bytes put on the wire by sync traffic,
+/// so different `sync_method`s can be compared for transmission cost.
+pub type History = usize;
+
+/// The largest sync-byte total seen on any explored path, surfaced in the JSON
+/// report so the transmission cost of each `sync_method` can be compared across
+/// runs. Process-global because it aggregates over the whole check.
+pub static MAX_SYNC_BYTES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// The names of every property registered on the model, in order, so the
+/// reporter can record a pass/fail status for each one.
+pub const PROPERTY_NAMES: &[&str] = &[
+    "transaction atomicity: no peer observes a partial batch",
+    "convergence: quiescent live servers are byte-identical",
+    "counter commutativity: converged value is the sum of deltas",
+    "text index: characters never exceed the number inserted",
+];
+
+/// Configuration threaded into the [`ActorModel`] and read by the properties.
+#[derive(Clone, Debug)]
+pub struct ModelConfig {
+    pub object_type: ObjectType,
+    pub server_count: usize,
+    pub server_lifecycle: ServerLifecycle,
+    /// The key pairs written together inside a batch transaction.
+    pub batch_keys: Vec<(String, String)>,
+    /// The sum of every delta applied by the increment clients.
+    pub counter_sum: i64,
+    /// The total number of text characters inserted by the text clients.
+    pub text_inserts: usize,
+}
+
+/// Builder describing the shape of the model to check.
+pub struct ModelBuilder {
+    pub put_clients: usize,
+    pub delete_clients: usize,
+    pub insert_clients: usize,
+    pub batch_clients: usize,
+    pub increment_clients: usize,
+    pub text_clients: usize,
+    pub servers: usize,
+    pub sync_method: SyncMethod,
+    pub message_acks: bool,
+    pub object_type: ObjectType,
+    pub server_lifecycle: ServerLifecycle,
+}
+
+impl ModelBuilder {
+    /// Turn the configuration into a checkable [`ActorModel`].
+    pub fn into_actor_model(self) -> ActorModel<MyRegisterActor, ModelConfig, History> {
+        let mut actors = Vec::new();
+
+        // Under Dynamic membership one extra server joins an already-running
+        // cluster; it is present in the id space from the start but down until
+        // a Join transition brings it up.
+        let joining = matches!(self.server_lifecycle, ServerLifecycle::Dynamic) as usize;
+        let total_servers = self.servers + joining;
+
+        // Servers occupy the first ids so clients can address `Id::from(0)`.
+        for i in 0..total_servers {
+            actors.push(MyRegisterActor::Server(Server {
+                peers: model_peers(i, total_servers),
+                sync_method: self.sync_method,
+                message_acks: self.message_acks,
+                joins_late: i >= self.servers,
+            }));
+        }
+
+        for i in 0..self.put_clients {
+            actors.push(MyRegisterActor::Putter(MapSinglePutter {
+                key: format!("key{i}"),
+                value: "value".to_owned(),
+                request_count: 2,
+            }));
+        }
+        for i in 0..self.delete_clients {
+            actors.push(MyRegisterActor::MapDeleter(MapSingleDeleter {
+                key: format!("key{i}"),
+                request_count: 2,
+            }));
+        }
+        for i in 0..self.insert_clients {
+            actors.push(MyRegisterActor::Inserter(ListInserter {
+                index: i,
+                value: "value".to_owned(),
+                request_count: 2,
+            }));
+            actors.push(MyRegisterActor::ListDeleter(ListDeleter {
+                index: i,
+                request_count: 2,
+            }));
+        }
+
+        let mut batch_keys = Vec::new();
+        for i in 0..self.batch_clients {
+            let key = format!("batch-a{i}");
+            let other_key = format!("batch-b{i}");
+            batch_keys.push((key.clone(), other_key.clone()));
+            actors.push(MyRegisterActor::Batch(BatchClient {
+                key,
+                other_key,
+                value: "value".to_owned(),
+                request_count: 1,
+            }));
+        }
+
+        let mut counter_sum = 0;
+        for i in 0..self.increment_clients {
+            let delta = i as i64 + 1;
+            let request_count = 2;
+            counter_sum += delta * request_count as i64;
+            actors.push(MyRegisterActor::Incrementer(IncrementClient {
+                delta,
+                request_count,
+            }));
+        }
+
+        let mut text_inserts = 0;
+        for _ in 0..self.text_clients {
+            let request_count = 2;
+            text_inserts += request_count;
+            actors.push(MyRegisterActor::TextInserter(TextInserter {
+                index: 0,
+                value: "a".to_owned(),
+                request_count,
+            }));
+            actors.push(MyRegisterActor::TextDeleter(TextDeleter {
+                index: 0,
+                request_count,
+            }));
+        }
+
+        // Inject membership transitions; the network interleaves their
+        // delivery with client traffic so the checker explores the orderings.
+        let transitions = lifecycle_transitions(self.server_lifecycle, self.servers);
+        if !transitions.is_empty() {
+            actors.push(MyRegisterActor::Lifecycle(LifecycleDriver { transitions }));
+        }
+
+        let cfg = ModelConfig {
+            object_type: self.object_type,
+            server_count: total_servers,
+            server_lifecycle: self.server_lifecycle,
+            batch_keys,
+            counter_sum,
+            text_inserts,
+        };
+
+        ActorModel::new(cfg, 0)
+            .actors(actors)
+            .init_network(Network::new_unordered_nonduplicating([]))
+            // Tally sync bytes so the reduction `BloomSync` gives over full
+            // change broadcast can be measured and compared across configs.
+            .record_msg_out(|_cfg, history, envelope| {
+                if let Request::Sync(m) = &envelope.msg {
+                    let total = history + m.byte_len();
+                    MAX_SYNC_BYTES.fetch_max(total, std::sync::atomic::Ordering::Relaxed);
+                    Some(total)
+                } else {
+                    None
+                }
+            })
+            .property(
+                Expectation::Always,
+                "transaction atomicity: no peer observes a partial batch",
+                |model, state| {
+                    model.cfg.batch_keys.iter().all(|(a, b)| {
+                        servers(state).all(|s| s.doc.map_contains(a) == s.doc.map_contains(b))
+                    })
+                },
+            )
+            // Sync-method agnostic: holds for `BloomSync` and full-change
+            // broadcast alike. It compares the live servers of one run with
+            // each other; it does not compare the state one method reaches
+            // with the state the other reaches.
+            .property(
+                Expectation::Always,
+                "convergence: quiescent live servers are byte-identical",
+                |_model, state| {
+                    if !is_quiescent(state) {
+                        return true;
+                    }
+                    let mut live = servers(state).filter(|s| !s.crashed).map(|s| &s.doc);
+                    match live.next() {
+                        Some(first) => live.all(|d| d == first),
+                        None => true,
+                    }
+                },
+            )
+            // Counter: increments are commutative, so once quiescent every
+            // live server's counter equals the plain integer sum of the deltas
+            // (checked when no crash can have dropped one).
+            .property(
+                Expectation::Always,
+                "counter commutativity: converged value is the sum of deltas",
+                |model, state| {
+                    if model.cfg.object_type != ObjectType::Counter || !is_quiescent(state) {
+                        return true;
+                    }
+                    let expected = model.cfg.counter_sum;
+                    let lossless = matches!(model.cfg.server_lifecycle, ServerLifecycle::Static);
+                    servers(state)
+                        .filter(|s| !s.crashed)
+                        .all(|s| !lossless || s.doc.counter() == expected)
+                },
+            )
+            // Text: like the list index invariants, a converged document never
+            // holds more characters than were ever inserted.
+            .property(
+                Expectation::Always,
+                "text index: characters never exceed the number inserted",
+                |model, state| {
+                    if model.cfg.object_type != ObjectType::Text {
+                        return true;
+                    }
+                    servers(state)
+                        .all(|s| s.doc.text_string().chars().count() <= model.cfg.text_inserts)
+                },
+            )
+    }
+}
+
+/// The membership transitions to drive for a given lifecycle mode. Server 0
+/// crashes and restarts; under Dynamic the late joiner (id `servers`) joins.
+fn lifecycle_transitions(mode: ServerLifecycle, servers: usize) -> Vec<(Id, Lifecycle)> {
+    let mut transitions = Vec::new();
+    match mode {
+        ServerLifecycle::Static => {}
+        ServerLifecycle::CrashRestart => {
+            transitions.push((Id::from(0), Lifecycle::Persist));
+            transitions.push((Id::from(0), Lifecycle::Crash));
+            transitions.push((Id::from(0), Lifecycle::Restart));
+        }
+        ServerLifecycle::Dynamic => {
+            transitions.push((Id::from(0), Lifecycle::Persist));
+            transitions.push((Id::from(0), Lifecycle::Crash));
+            transitions.push((Id::from(0), Lifecycle::Restart));
+            transitions.push((Id::from(servers), Lifecycle::Join));
+        }
+    }
+    transitions
+}
+
+/// Whether no messages remain in flight — a quiescent, terminal state.
+pub fn is_quiescent<H>(state: &stateright::actor::ActorModelState<MyRegisterActor, H>) -> bool {
+    state.network.iter_all().next().is_none()
+}
+
+/// Iterate over the server states of a model state.
+pub fn servers<H>(
+    state: &stateright::actor::ActorModelState<MyRegisterActor, H>,
+) -> impl Iterator<Item = &crate::server::ServerState> {
+    state.actor_states.iter().filter_map(|s| match &**s {
+        MyRegisterActorState::Server(server_state) => Some(server_state),
+        MyRegisterActorState::Client => None,
+    })
+}
+
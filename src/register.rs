@@ -0,0 +1,124 @@
+use std::borrow::Cow;
+
+use stateright::actor::{Actor, Id, Out};
+
+use crate::client::{
+    BatchClient, IncrementClient, ListDeleter, ListInserter, MapSingleDeleter, MapSinglePutter,
+    Request, TextDeleter, TextInserter,
+};
+use crate::server::{Lifecycle, Server, ServerState};
+
+/// The single actor type the model is built from: every client strategy plus
+/// the server, unified so they can share one `Msg`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MyRegisterActor {
+    Putter(MapSinglePutter),
+    MapDeleter(MapSingleDeleter),
+    Inserter(ListInserter),
+    ListDeleter(ListDeleter),
+    Batch(BatchClient),
+    Incrementer(IncrementClient),
+    TextInserter(TextInserter),
+    TextDeleter(TextDeleter),
+    Server(Server),
+    /// Drives membership transitions against the servers.
+    Lifecycle(LifecycleDriver),
+}
+
+/// An actor that injects crash/restart/join transitions, letting the network
+/// interleave them with client traffic for the checker to explore.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LifecycleDriver {
+    /// The transitions to emit, each aimed at a server id.
+    pub transitions: Vec<(Id, Lifecycle)>,
+}
+
+/// The per-actor state: clients are stateless, the server carries its document.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MyRegisterActorState {
+    Client,
+    Server(ServerState),
+}
+
+impl Actor for MyRegisterActor {
+    type Msg = Request;
+
+    type State = MyRegisterActorState;
+
+    fn on_start(&self, _id: Id, o: &mut Out<Self>) -> Self::State {
+        match self {
+            MyRegisterActor::Putter(a) => {
+                for _ in 0..a.request_count {
+                    o.send(Id::from(0), Request::PutMap(a.key.clone(), a.value.clone()));
+                }
+                MyRegisterActorState::Client
+            }
+            MyRegisterActor::MapDeleter(a) => {
+                for _ in 0..a.request_count {
+                    o.send(Id::from(0), Request::DeleteMap(a.key.clone()));
+                }
+                MyRegisterActorState::Client
+            }
+            MyRegisterActor::Inserter(a) => {
+                for _ in 0..a.request_count {
+                    o.send(Id::from(0), Request::InsertList(a.index, a.value.clone()));
+                }
+                MyRegisterActorState::Client
+            }
+            MyRegisterActor::ListDeleter(a) => {
+                for _ in 0..a.request_count {
+                    o.send(Id::from(0), Request::DeleteList(a.index));
+                }
+                MyRegisterActorState::Client
+            }
+            MyRegisterActor::Batch(a) => {
+                for _ in 0..a.request_count {
+                    o.send(Id::from(0), Request::Transaction(a.ops()));
+                }
+                MyRegisterActorState::Client
+            }
+            MyRegisterActor::Incrementer(a) => {
+                for _ in 0..a.request_count {
+                    o.send(Id::from(0), Request::Increment(a.delta));
+                }
+                MyRegisterActorState::Client
+            }
+            MyRegisterActor::TextInserter(a) => {
+                for _ in 0..a.request_count {
+                    o.send(Id::from(0), Request::InsertText(a.index, a.value.clone()));
+                }
+                MyRegisterActorState::Client
+            }
+            MyRegisterActor::TextDeleter(a) => {
+                for _ in 0..a.request_count {
+                    o.send(Id::from(0), Request::DeleteText(a.index));
+                }
+                MyRegisterActorState::Client
+            }
+            MyRegisterActor::Lifecycle(d) => {
+                for (dst, transition) in &d.transitions {
+                    o.send(*dst, Request::Lifecycle(*transition));
+                }
+                MyRegisterActorState::Client
+            }
+            MyRegisterActor::Server(s) => MyRegisterActorState::Server(s.initial_state()),
+        }
+    }
+
+    fn on_msg(
+        &self,
+        _id: Id,
+        state: &mut Cow<Self::State>,
+        src: Id,
+        msg: Self::Msg,
+        o: &mut Out<Self>,
+    ) {
+        if let MyRegisterActor::Server(s) = self {
+            if let MyRegisterActorState::Server(server_state) = state.to_mut() {
+                for (dst, reply) in s.handle(server_state, src, &msg) {
+                    o.send(dst, reply);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde_json::json;
+use stateright::report::ReportData;
+use stateright::Checker;
+use stateright::Model;
+
+use crate::model::PROPERTY_NAMES;
+use crate::ReportFormat;
+
+/// Reports checking progress, either as human-oriented console output or, in
+/// `json` mode, by staying quiet until a single structured record is emitted on
+/// completion (see [`emit`]).
+pub struct Reporter {
+    format: ReportFormat,
+    /// The most recent progress snapshot.
+    last: Option<ReportData>,
+}
+
+impl Reporter {
+    pub fn new(format: ReportFormat) -> Self {
+        Self { format, last: None }
+    }
+}
+
+impl Default for Reporter {
+    fn default() -> Self {
+        Self::new(ReportFormat::Text)
+    }
+}
+
+impl<M> stateright::report::Reporter<M> for Reporter
+where
+    M: Model,
+    M::Action: std::fmt::Debug,
+    M::State: std::fmt::Debug,
+{
+    fn report_checking(&mut self, data: ReportData) {
+        if let ReportFormat::Text = self.format {
+            println!(
+                "checked {} states ({} unique), max depth {}, {:?} elapsed",
+                data.total_states, data.unique_states, data.max_depth, data.duration,
+            );
+        }
+        self.last = Some(data);
+    }
+
+    fn report_discoveries(
+        &mut self,
+        discoveries: BTreeMap<&'static str, stateright::report::ReportDiscovery<M>>,
+    ) {
+        if let ReportFormat::Text = self.format {
+            for (name, discovery) in discoveries {
+                println!("discovered \"{name}\" {}", discovery.classification);
+                for step in discovery.path.into_actions() {
+                    println!("  - {step:?}");
+                }
+            }
+        }
+    }
+}
+
+/// Emit a structured JSON record of the completed check to stdout (or `output`
+/// when set), aggregating state counts, depth, elapsed time, and the pass/fail
+/// status of every property with the counterexample path the checker found for
+/// any that were violated. Under BFS that path is a shortest one; under DFS it
+/// need not be. A no-op unless the `json` format was selected.
+pub fn emit<M>(
+    format: ReportFormat,
+    output: Option<&std::path::Path>,
+    elapsed: Duration,
+    checker: &impl Checker<M>,
+) where
+    M: Model,
+    M::Action: std::fmt::Debug,
+{
+    if let ReportFormat::Text = format {
+        return;
+    }
+
+    let discoveries = checker.discoveries();
+    let properties = PROPERTY_NAMES
+        .iter()
+        .map(|name| {
+            match discoveries.iter().find(|(found, _)| **found == **name) {
+                Some((_, path)) => {
+                    let steps: Vec<String> = path
+                        .clone()
+                        .into_actions()
+                        .iter()
+                        .map(|a| format!("{a:?}"))
+                        .collect();
+                    json!({ "name": name, "status": "fail", "counterexample": steps })
+                }
+                None => json!({ "name": name, "status": "pass" }),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let record = json!({
+        "total_states": checker.generated_count(),
+        "unique_states": checker.unique_state_count(),
+        "max_depth": checker.max_depth(),
+        "elapsed_ms": elapsed.as_millis(),
+        // Peak sync bytes on any explored path; compare across --sync-method
+        // runs to confirm BloomSync transmits fewer bytes than full broadcast.
+        "max_sync_bytes": crate::model::MAX_SYNC_BYTES.load(std::sync::atomic::Ordering::Relaxed),
+        "properties": properties,
+    });
+
+    let document = serde_json::to_string_pretty(&record).unwrap();
+    match output {
+        Some(path) => std::fs::write(path, document).unwrap(),
+        None => println!("{document}"),
+    }
+}
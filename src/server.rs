@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+
+use stateright::actor::Id;
+
+use crate::client::{Op, Request, Response};
+use crate::doc::Doc;
+
+/// How servers propagate changes to one another.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, clap::ArgEnum)]
+pub enum SyncMethod {
+    /// Broadcast the full set of changes on every local mutation.
+    Changes,
+    /// Automerge's two-party Bloom-filter handshake: exchange heads and a
+    /// filter, send back only the changes the other side appears to lack, and
+    /// follow up with need-requests until both sides' heads agree.
+    BloomSync,
+}
+
+/// Sync traffic exchanged between servers.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SyncMessage {
+    /// A raw, self-contained dump of all of the sender's changes.
+    Changes(Vec<u8>),
+    /// An encoded Automerge sync message (heads + Bloom filter + changes).
+    Bloom(Vec<u8>),
+}
+
+impl SyncMessage {
+    /// The number of bytes this message puts on the wire.
+    pub fn byte_len(&self) -> usize {
+        match self {
+            SyncMessage::Changes(b) | SyncMessage::Bloom(b) => b.len(),
+        }
+    }
+}
+
+/// A membership transition driving a server's lifecycle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Lifecycle {
+    /// Crash: drop in-flight work and any state not yet persisted.
+    Crash,
+    /// Persist the current document to the save that survives a crash.
+    Persist,
+    /// Restart a crashed server from its persisted save.
+    Restart,
+    /// Bring a late server into an already-diverged cluster.
+    Join,
+}
+
+/// A server replicating an Automerge document to its peers.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Server {
+    /// The other servers this one syncs with.
+    pub peers: Vec<Id>,
+    /// How to propagate changes.
+    pub sync_method: SyncMethod,
+    /// Whether to acknowledge client requests.
+    pub message_acks: bool,
+    /// Whether this server joins the cluster late rather than at start.
+    pub joins_late: bool,
+}
+
+/// The replicated state a server carries between steps.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ServerState {
+    pub doc: Doc,
+    /// The last persisted snapshot a crash would restore from.
+    pub saved: Vec<u8>,
+    /// Whether the server is currently down (crashed or not yet joined).
+    pub crashed: bool,
+    /// Encoded per-peer Automerge sync state, used by `BloomSync`.
+    pub sync_states: BTreeMap<Id, Vec<u8>>,
+}
+
+impl Server {
+    /// The initial state of a freshly started server.
+    pub fn initial_state(&self) -> ServerState {
+        let mut doc = Doc::default();
+        let saved = doc.save();
+        ServerState {
+            doc,
+            saved,
+            crashed: self.joins_late,
+            sync_states: BTreeMap::new(),
+        }
+    }
+
+    /// Apply a single client operation to the document.
+    fn apply_op(doc: &mut Doc, op: &Op) {
+        match op {
+            Op::PutMap(k, v) => doc.put_map(k, v),
+            Op::DeleteMap(k) => doc.delete_map(k),
+            Op::InsertList(i, v) => doc.insert_list(*i, v),
+            Op::DeleteList(i) => doc.delete_list(*i),
+        }
+    }
+
+    /// The messages broadcasting the current document to every peer.
+    fn broadcast(&self, state: &mut ServerState) -> Vec<(Id, Request)> {
+        match self.sync_method {
+            SyncMethod::Changes => {
+                let bytes = state.doc.save();
+                self.peers
+                    .iter()
+                    .map(|peer| (*peer, Request::Sync(SyncMessage::Changes(bytes.clone()))))
+                    .collect()
+            }
+            SyncMethod::BloomSync => self
+                .peers
+                .iter()
+                .filter_map(|peer| Self::sync_step(state, *peer))
+                .collect(),
+        }
+    }
+
+    /// Run one step of the Bloom handshake with `peer`, producing the sync
+    /// message to send (heads + filter, or the changes/need-request reply), if
+    /// the two sides are not already in agreement.
+    fn sync_step(state: &mut ServerState, peer: Id) -> Option<(Id, Request)> {
+        let mut sync_state = decode_sync_state(&state.sync_states, peer);
+        let message = state.doc.generate_sync_message(&mut sync_state);
+        state
+            .sync_states
+            .insert(peer, sync_state.encode());
+        message.map(|m| (peer, Request::Sync(SyncMessage::Bloom(m.encode()))))
+    }
+
+    /// Handle a sync message from `src`, returning any follow-up sync to emit.
+    fn receive_sync(&self, state: &mut ServerState, src: Id, msg: &SyncMessage) -> Vec<(Id, Request)> {
+        match msg {
+            SyncMessage::Changes(bytes) => {
+                let mut other = Doc::load(bytes);
+                let other_heads = other.heads();
+                state.doc.merge(&mut other);
+                // Pull side of catch-up: if we hold changes the sender lacks
+                // (our heads moved past the document they sent), push ours back
+                // so a restarted/joined node and its peers converge rather than
+                // leaving the exchange one-directional. The exchange terminates
+                // because the reply leaves both sides on the merged heads.
+                if state.doc.heads() != other_heads {
+                    vec![(src, Request::Sync(SyncMessage::Changes(state.doc.save())))]
+                } else {
+                    Vec::new()
+                }
+            }
+            SyncMessage::Bloom(bytes) => {
+                let message = automerge::sync::Message::decode(bytes).unwrap();
+                let mut sync_state = decode_sync_state(&state.sync_states, src);
+                state.doc.receive_sync_message(&mut sync_state, message);
+                state.sync_states.insert(src, sync_state.encode());
+                // Keep exchanging until the heads agree (resolves Bloom false
+                // positives via the follow-up need-request round).
+                Self::sync_step(state, src).into_iter().collect()
+            }
+        }
+    }
+
+    /// Handle a message, mutating `state` and returning the messages to emit.
+    pub fn handle(&self, state: &mut ServerState, src: Id, msg: &Request) -> Vec<(Id, Request)> {
+        let mut out = Vec::new();
+        // A lifecycle transition is the only thing a crashed server reacts to;
+        // every other message is dropped, modelling lost in-flight work.
+        if let Request::Lifecycle(transition) = msg {
+            return self.lifecycle(state, *transition);
+        }
+        if state.crashed {
+            return out;
+        }
+        match msg {
+            Request::PutMap(k, v) => {
+                state.doc.put_map(k, v);
+                out.extend(self.broadcast(state));
+                self.ack(src, Response::PutOk, &mut out);
+            }
+            Request::DeleteMap(k) => {
+                state.doc.delete_map(k);
+                out.extend(self.broadcast(state));
+                self.ack(src, Response::DeleteOk, &mut out);
+            }
+            Request::InsertList(i, v) => {
+                state.doc.insert_list(*i, v);
+                out.extend(self.broadcast(state));
+                self.ack(src, Response::InsertOk, &mut out);
+            }
+            Request::DeleteList(i) => {
+                state.doc.delete_list(*i);
+                out.extend(self.broadcast(state));
+                self.ack(src, Response::DeleteOk, &mut out);
+            }
+            Request::Transaction(ops) => {
+                // Apply the whole vector inside one transaction and commit once,
+                // so peers only ever observe all of the ops or none of them.
+                state.doc.transact(|doc| {
+                    for op in ops {
+                        Self::apply_op(doc, op);
+                    }
+                });
+                out.extend(self.broadcast(state));
+                self.ack(src, Response::TransactionOk, &mut out);
+            }
+            Request::Increment(delta) => {
+                state.doc.increment(*delta);
+                out.extend(self.broadcast(state));
+                self.ack(src, Response::IncrementOk, &mut out);
+            }
+            Request::InsertText(i, v) => {
+                state.doc.insert_text(*i, v);
+                out.extend(self.broadcast(state));
+                self.ack(src, Response::TextOk, &mut out);
+            }
+            Request::DeleteText(i) => {
+                state.doc.delete_text(*i);
+                out.extend(self.broadcast(state));
+                self.ack(src, Response::TextOk, &mut out);
+            }
+            Request::Sync(sync) => {
+                out.extend(self.receive_sync(state, src, sync));
+            }
+            Request::Response(_) => {}
+            Request::Lifecycle(_) => {}
+        }
+        out
+    }
+
+    /// Apply a lifecycle transition, returning any catch-up sync to emit.
+    fn lifecycle(&self, state: &mut ServerState, transition: Lifecycle) -> Vec<(Id, Request)> {
+        match transition {
+            Lifecycle::Persist => {
+                state.saved = state.doc.save();
+                Vec::new()
+            }
+            Lifecycle::Crash => {
+                // Lose everything since the last persist.
+                state.doc = Doc::load(&state.saved);
+                state.crashed = true;
+                Vec::new()
+            }
+            Lifecycle::Restart | Lifecycle::Join => {
+                state.doc = Doc::load(&state.saved);
+                state.crashed = false;
+                // Catch up by broadcasting our state so peers reconcile us.
+                self.broadcast(state)
+            }
+        }
+    }
+
+    fn ack(&self, src: Id, response: Response, out: &mut Vec<(Id, Request)>) {
+        if self.message_acks {
+            out.push((src, Request::Response(response)));
+        }
+    }
+}
+
+/// Decode the stored per-peer sync state, starting fresh if none is stored yet.
+fn decode_sync_state(
+    states: &BTreeMap<Id, Vec<u8>>,
+    peer: Id,
+) -> automerge::sync::State {
+    match states.get(&peer) {
+        Some(bytes) => automerge::sync::State::decode(bytes).unwrap(),
+        None => automerge::sync::State::new(),
+    }
+}